@@ -0,0 +1,106 @@
+// src/cipher.rs
+use std::fmt;
+
+const CAT_WORDS: [&str; 16] = [
+    "meow", "mew", "purr", "hiss", "mrow", "nya", "prrp", "mow", "meww", "rawr", "mrr", "psps",
+    "chirp", "trill", "yowl", "squeak",
+];
+
+const DOG_WORDS: [&str; 16] = [
+    "woof", "bark", "arf", "growl", "yip", "ruff", "howl", "grr", "bork", "snarl", "yelp",
+    "whine", "sniff", "pant", "wag", "awoo",
+];
+
+/// The two themes a `.purr` container can be written in. Both encode the
+/// same bytes; the dialect only changes which words are on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CipherDialect {
+    Cat,
+    Dog,
+}
+
+impl CipherDialect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CipherDialect::Cat => "cat",
+            CipherDialect::Dog => "dog",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "cat" => Some(CipherDialect::Cat),
+            "dog" => Some(CipherDialect::Dog),
+            _ => None,
+        }
+    }
+
+    fn words(&self) -> &'static [&'static str; 16] {
+        match self {
+            CipherDialect::Cat => &CAT_WORDS,
+            CipherDialect::Dog => &DOG_WORDS,
+        }
+    }
+}
+
+impl fmt::Display for CipherDialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Encodes raw bytes as a whitespace-separated stream of dialect words, two
+/// words (high nibble, then low nibble) per byte.
+pub fn encode(bytes: &[u8], dialect: CipherDialect) -> String {
+    let words = dialect.words();
+    bytes
+        .iter()
+        .flat_map(|b| [words[(b >> 4) as usize], words[(b & 0x0f) as usize]])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Decodes a word stream produced by [`encode`] back into raw bytes.
+pub fn decode(stream: &str, dialect: CipherDialect) -> Result<Vec<u8>, String> {
+    let words = dialect.words();
+    let nibbles = stream
+        .split_whitespace()
+        .map(|word| {
+            words
+                .iter()
+                .position(|w| *w == word)
+                .map(|n| n as u8)
+                .ok_or_else(|| format!("unrecognized {} token: '{}'", dialect, word))
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+
+    if nibbles.len() % 2 != 0 {
+        return Err("odd number of tokens in cipher stream".to_string());
+    }
+
+    Ok(nibbles
+        .chunks(2)
+        .map(|pair| (pair[0] << 4) | pair[1])
+        .collect())
+}
+
+/// Summary statistics over a [`encode`]d token stream, used by `purr
+/// inspect` to describe a container's body; computed from the words alone,
+/// without decoding them into bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenStats {
+    pub total_tokens: usize,
+    pub distinct_tokens: usize,
+}
+
+/// Computes [`TokenStats`] for a raw (still-encoded) cipher-token stream.
+pub fn stats(stream: &str) -> TokenStats {
+    let tokens: Vec<&str> = stream.split_whitespace().collect();
+    let mut distinct: Vec<&str> = tokens.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+    TokenStats {
+        total_tokens: tokens.len(),
+        distinct_tokens: distinct.len(),
+    }
+}
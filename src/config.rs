@@ -0,0 +1,99 @@
+// src/config.rs
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+/// The dialect used for encryption when `--dialect` isn't passed explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferredDialect {
+    Cat,
+    Dog,
+}
+
+/// Reads and writes `~/.purr/config`, a small `key=value` file. Besides the
+/// preferred dialect, it can hold `pre_encrypt`/`post_encrypt`/
+/// `pre_decrypt`/`post_decrypt` shell commands that `run` dispatches around
+/// the matching operation (see passage's `pre_load`/`post_save` hooks).
+pub struct ConfigManager {
+    config_path: PathBuf,
+    dialect: PreferredDialect,
+    pre_encrypt: Option<String>,
+    post_encrypt: Option<String>,
+    pre_decrypt: Option<String>,
+    post_decrypt: Option<String>,
+}
+
+impl ConfigManager {
+    pub fn new(home_dir: &std::path::Path) -> io::Result<Self> {
+        let config_path = home_dir.join("config");
+        let values = if config_path.exists() {
+            parse_config(&fs::read_to_string(&config_path)?)
+        } else {
+            BTreeMap::new()
+        };
+
+        let dialect = match values.get("dialect").map(String::as_str) {
+            Some("dog") => PreferredDialect::Dog,
+            _ => PreferredDialect::Cat,
+        };
+
+        Ok(ConfigManager {
+            config_path,
+            dialect,
+            pre_encrypt: values.get("pre_encrypt").cloned(),
+            post_encrypt: values.get("post_encrypt").cloned(),
+            pre_decrypt: values.get("pre_decrypt").cloned(),
+            post_decrypt: values.get("post_decrypt").cloned(),
+        })
+    }
+
+    pub fn get_dialect(&self) -> PreferredDialect {
+        self.dialect
+    }
+
+    pub fn set_dialect(&mut self, dialect: PreferredDialect) -> io::Result<()> {
+        self.dialect = dialect;
+        self.save()
+    }
+
+    pub fn pre_encrypt_hook(&self) -> Option<&str> {
+        self.pre_encrypt.as_deref()
+    }
+
+    pub fn post_encrypt_hook(&self) -> Option<&str> {
+        self.post_encrypt.as_deref()
+    }
+
+    pub fn pre_decrypt_hook(&self) -> Option<&str> {
+        self.pre_decrypt.as_deref()
+    }
+
+    pub fn post_decrypt_hook(&self) -> Option<&str> {
+        self.post_decrypt.as_deref()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let dialect_str = match self.dialect {
+            PreferredDialect::Cat => "cat",
+            PreferredDialect::Dog => "dog",
+        };
+        let mut contents = format!("dialect={dialect_str}\n");
+        for (key, hook) in [
+            ("pre_encrypt", &self.pre_encrypt),
+            ("post_encrypt", &self.post_encrypt),
+            ("pre_decrypt", &self.pre_decrypt),
+            ("post_decrypt", &self.post_decrypt),
+        ] {
+            if let Some(hook) = hook {
+                contents.push_str(&format!("{key}={hook}\n"));
+            }
+        }
+        fs::write(&self.config_path, contents)
+    }
+}
+
+fn parse_config(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
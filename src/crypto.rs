@@ -0,0 +1,385 @@
+// src/crypto.rs
+use crate::cipher::{self, CipherDialect};
+use crate::keys::KeyPair;
+use bip39::{Language, Mnemonic};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Nonce,
+};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::{
+    io::{self, Read, Write},
+    path::Path,
+};
+use x25519_dalek::{PublicKey, SharedSecret, StaticSecret};
+
+const MAGIC: &str = "PURR1";
+const MNEMONIC_WORD_COUNT: usize = 24;
+
+pub fn generate_keypair(pub_path: &Path, priv_path: &Path) -> io::Result<()> {
+    KeyPair::generate().save(pub_path, priv_path)
+}
+
+/// Generates a fresh BIP-39 recovery phrase and the 64-byte seed derived
+/// from it (PBKDF2-HMAC-SHA512, 2048 iterations, no extra passphrase).
+pub fn generate_mnemonic_seed() -> io::Result<(String, [u8; 64])> {
+    let mnemonic =
+        Mnemonic::generate_in(Language::English, MNEMONIC_WORD_COUNT).map_err(|e| other(&e.to_string()))?;
+    Ok((mnemonic.to_string(), mnemonic.to_seed("")))
+}
+
+/// Validates a recovery phrase (including its checksum) and re-derives the
+/// same 64-byte seed `generate_mnemonic_seed` would have produced for it.
+pub fn seed_from_mnemonic(phrase: &str) -> io::Result<[u8; 64]> {
+    let mnemonic = Mnemonic::parse_in(Language::English, phrase).map_err(|e| invalid(&e.to_string()))?;
+    Ok(mnemonic.to_seed(""))
+}
+
+/// Deterministically derives a keypair from mnemonic seed material, so the
+/// same phrase always reproduces the same keys.
+pub fn generate_keypair_from_seed(
+    pub_path: &Path,
+    priv_path: &Path,
+    seed: &[u8; 64],
+) -> io::Result<()> {
+    KeyPair::generate_deterministic(seed).save(pub_path, priv_path)
+}
+
+fn derive_wrap_key(shared: &SharedSecret) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"purrcrypt-wrap-key");
+    hasher.update(shared.as_bytes());
+    hasher.finalize().into()
+}
+
+/// The hex fingerprint a given key's raw bytes hash to. Used both to label
+/// recipient blocks in a container and to match `list-keys --fingerprint`.
+pub fn fingerprint(key_bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(key_bytes))
+}
+
+/// Encrypts whatever `input` yields and writes a `.purr` container to
+/// `output`. The message is encrypted once under a random session key, and
+/// that session key is then wrapped separately for each of
+/// `recipient_public_keys`, so any one of the matching private keys can
+/// decrypt it. Accepts any reader/writer, so callers can pass an open file,
+/// `Stdin`/`Stdout`, or anything else that implements `Read`/`Write`.
+pub fn encrypt_file(
+    mut input: impl Read,
+    mut output: impl Write,
+    recipient_public_keys: &[PublicKey],
+    dialect: CipherDialect,
+) -> io::Result<()> {
+    if recipient_public_keys.is_empty() {
+        return Err(invalid("at least one recipient is required"));
+    }
+
+    let mut plaintext = Vec::new();
+    input.read_to_end(&mut plaintext)?;
+
+    let mut session_key = [0u8; 32];
+    OsRng.fill_bytes(&mut session_key);
+
+    let ephemeral_secret = StaticSecret::random_from_rng(OsRng);
+    let ephemeral_public = PublicKey::from(&ephemeral_secret);
+
+    let mut recipient_blocks = Vec::with_capacity(recipient_public_keys.len());
+    for recipient_public_key in recipient_public_keys {
+        let wrap_key = derive_wrap_key(&ephemeral_secret.diffie_hellman(recipient_public_key));
+        let wrap_aead = ChaCha20Poly1305::new((&wrap_key).into());
+        let mut wrap_nonce_bytes = [0u8; 12];
+        OsRng.fill_bytes(&mut wrap_nonce_bytes);
+        let wrapped_key = wrap_aead
+            .encrypt(Nonce::from_slice(&wrap_nonce_bytes), session_key.as_ref())
+            .map_err(|e| other(&format!("key wrap failed: {e}")))?;
+        recipient_blocks.push(format!(
+            "{} {} {}",
+            fingerprint(recipient_public_key.as_bytes()),
+            hex::encode(wrap_nonce_bytes),
+            hex::encode(wrapped_key)
+        ));
+    }
+
+    let aead = ChaCha20Poly1305::new((&session_key).into());
+    let mut nonce_bytes = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = aead
+        .encrypt(nonce, plaintext.as_ref())
+        .map_err(|e| other(&format!("encryption failed: {e}")))?;
+
+    let mut contents = format!(
+        "{MAGIC} {dialect} {}\n{}\n",
+        recipient_blocks.len(),
+        hex::encode(ephemeral_public.as_bytes()),
+    );
+    for block in &recipient_blocks {
+        contents.push_str(block);
+        contents.push('\n');
+    }
+    contents.push_str(&hex::encode(nonce_bytes));
+    contents.push('\n');
+    contents.push_str(&cipher::encode(&ciphertext, dialect));
+    contents.push('\n');
+
+    output.write_all(contents.as_bytes())
+}
+
+/// Reads a `.purr` container from `input` and writes the recovered plaintext
+/// to `output`. Tries `keypair`'s private key against each recipient key
+/// block in turn until one unwraps the session key. Accepts any
+/// reader/writer, so callers can pass an open file, `Stdin`/`Stdout`, or
+/// anything else that implements `Read`/`Write`.
+pub fn decrypt_file(
+    mut input: impl Read,
+    mut output: impl Write,
+    keypair: &KeyPair,
+) -> io::Result<()> {
+    let mut contents = String::new();
+    input.read_to_string(&mut contents)?;
+    let mut lines = contents.lines();
+
+    let header = lines.next().ok_or_else(|| invalid("empty container"))?;
+    let mut header_parts = header.split_whitespace();
+    if header_parts.next() != Some(MAGIC) {
+        return Err(invalid("not a purr container"));
+    }
+    let dialect = header_parts
+        .next()
+        .and_then(CipherDialect::from_str)
+        .ok_or_else(|| invalid("missing or unknown dialect"))?;
+    let recipient_count: usize = header_parts
+        .next()
+        .ok_or_else(|| invalid("missing recipient count"))?
+        .parse()
+        .map_err(|_| invalid("invalid recipient count"))?;
+
+    let ephemeral_hex = lines.next().ok_or_else(|| invalid("missing ephemeral key"))?;
+    let ephemeral_bytes: [u8; 32] = hex::decode(ephemeral_hex)
+        .map_err(|e| invalid(&e.to_string()))?
+        .try_into()
+        .map_err(|_| invalid("bad ephemeral key length"))?;
+    let ephemeral_public = PublicKey::from(ephemeral_bytes);
+
+    let wrap_key = derive_wrap_key(&keypair.private.diffie_hellman(&ephemeral_public));
+    let wrap_aead = ChaCha20Poly1305::new((&wrap_key).into());
+
+    let mut session_key: Option<[u8; 32]> = None;
+    for _ in 0..recipient_count {
+        // Every block must still be consumed from `lines` to keep the reader
+        // aligned for the nonce/body that follow, but once we've already
+        // unwrapped the session key there's no need to decode or attempt to
+        // decrypt the remaining blocks.
+        let line = lines
+            .next()
+            .ok_or_else(|| invalid("missing recipient key block"))?;
+        if session_key.is_some() {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        fields.next(); // recipient fingerprint; only used by `inspect`
+        let nonce_hex = fields
+            .next()
+            .ok_or_else(|| invalid("malformed recipient key block"))?;
+        let wrapped_hex = fields
+            .next()
+            .ok_or_else(|| invalid("malformed recipient key block"))?;
+        let wrap_nonce = hex::decode(nonce_hex).map_err(|e| invalid(&e.to_string()))?;
+        let wrapped = hex::decode(wrapped_hex).map_err(|e| invalid(&e.to_string()))?;
+
+        if let Ok(unwrapped) = wrap_aead.decrypt(Nonce::from_slice(&wrap_nonce), wrapped.as_ref()) {
+            session_key = unwrapped.try_into().ok();
+        }
+    }
+    let session_key = session_key.ok_or_else(|| {
+        invalid("this private key does not unwrap any recipient block in the container")
+    })?;
+
+    let nonce_hex = lines.next().ok_or_else(|| invalid("missing nonce"))?;
+    let body = lines.next().unwrap_or("");
+
+    let nonce_bytes = hex::decode(nonce_hex).map_err(|e| invalid(&e.to_string()))?;
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let aead = ChaCha20Poly1305::new((&session_key).into());
+    let ciphertext = cipher::decode(body, dialect).map_err(|e| invalid(&e))?;
+    let plaintext = aead
+        .decrypt(nonce, ciphertext.as_ref())
+        .map_err(|e| other(&format!("decryption failed: {e}")))?;
+
+    output.write_all(&plaintext)
+}
+
+/// A read-only description of a `.purr` container, as produced by
+/// [`inspect`]. Unlike [`decrypt_file`], this never decrypts the ciphertext
+/// or touches any private key - it only walks the container's structure and
+/// decodes the cipher-token body to measure it.
+#[derive(Debug)]
+pub struct ContainerInfo {
+    pub dialect: CipherDialect,
+    pub recipient_fingerprints: Vec<String>,
+    pub payload_len: usize,
+    pub token_stats: cipher::TokenStats,
+}
+
+/// Parses a `.purr` container's structure without decrypting it: dialect,
+/// the fingerprints of the keys it's wrapped for, the decoded payload
+/// length, and cipher-token stream statistics.
+pub fn inspect(mut input: impl Read) -> io::Result<ContainerInfo> {
+    let mut contents = String::new();
+    input.read_to_string(&mut contents)?;
+    let mut lines = contents.lines();
+
+    let header = lines.next().ok_or_else(|| invalid("empty container"))?;
+    let mut header_parts = header.split_whitespace();
+    if header_parts.next() != Some(MAGIC) {
+        return Err(invalid("not a purr container"));
+    }
+    let dialect = header_parts
+        .next()
+        .and_then(CipherDialect::from_str)
+        .ok_or_else(|| invalid("missing or unknown dialect"))?;
+    let recipient_count: usize = header_parts
+        .next()
+        .ok_or_else(|| invalid("missing recipient count"))?
+        .parse()
+        .map_err(|_| invalid("invalid recipient count"))?;
+
+    lines.next().ok_or_else(|| invalid("missing ephemeral key"))?;
+
+    let mut recipient_fingerprints = Vec::with_capacity(recipient_count);
+    for _ in 0..recipient_count {
+        let line = lines
+            .next()
+            .ok_or_else(|| invalid("missing recipient key block"))?;
+        let fingerprint = line
+            .split_whitespace()
+            .next()
+            .ok_or_else(|| invalid("malformed recipient key block"))?;
+        if fingerprint.len() != 64 || !fingerprint.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid("malformed recipient fingerprint"));
+        }
+        recipient_fingerprints.push(fingerprint.to_string());
+    }
+
+    lines.next().ok_or_else(|| invalid("missing nonce"))?;
+    let body = lines.next().unwrap_or("");
+
+    let payload_len = cipher::decode(body, dialect)
+        .map_err(|e| invalid(&e))?
+        .len();
+
+    Ok(ContainerInfo {
+        dialect,
+        recipient_fingerprints,
+        payload_len,
+        token_stats: cipher::stats(body),
+    })
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+fn other(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, msg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_roundtrips_for_every_recipient() {
+        let alice = KeyPair::generate();
+        let bob = KeyPair::generate();
+        let plaintext = b"meet at the usual spot";
+
+        let mut container = Vec::new();
+        encrypt_file(
+            &plaintext[..],
+            &mut container,
+            &[alice.public, bob.public],
+            CipherDialect::Cat,
+        )
+        .unwrap();
+
+        for keypair in [&alice, &bob] {
+            let mut output = Vec::new();
+            decrypt_file(&container[..], &mut output, keypair).unwrap();
+            assert_eq!(output, plaintext);
+        }
+    }
+
+    #[test]
+    fn decrypt_fails_for_a_key_outside_the_recipient_list() {
+        let alice = KeyPair::generate();
+        let mallory = KeyPair::generate();
+
+        let mut container = Vec::new();
+        encrypt_file(&b"secret"[..], &mut container, &[alice.public], CipherDialect::Dog).unwrap();
+
+        let mut output = Vec::new();
+        assert!(decrypt_file(&container[..], &mut output, &mallory).is_err());
+    }
+
+    #[test]
+    fn inspect_reports_structure_without_decrypting() {
+        let alice = KeyPair::generate();
+        let bob = KeyPair::generate();
+        let plaintext = b"hello world";
+
+        let mut container = Vec::new();
+        encrypt_file(
+            &plaintext[..],
+            &mut container,
+            &[alice.public, bob.public],
+            CipherDialect::Cat,
+        )
+        .unwrap();
+
+        let info = inspect(&container[..]).unwrap();
+        assert_eq!(info.dialect, CipherDialect::Cat);
+        assert_eq!(info.recipient_fingerprints.len(), 2);
+        assert!(info
+            .recipient_fingerprints
+            .contains(&fingerprint(alice.public.as_bytes())));
+        assert!(info
+            .recipient_fingerprints
+            .contains(&fingerprint(bob.public.as_bytes())));
+        assert_eq!(info.payload_len, plaintext.len() + 16); // + Poly1305 tag
+    }
+
+    #[test]
+    fn inspect_rejects_a_truncated_recipient_fingerprint() {
+        let alice = KeyPair::generate();
+        let mut container = Vec::new();
+        encrypt_file(&b"secret"[..], &mut container, &[alice.public], CipherDialect::Cat).unwrap();
+
+        let mut contents = String::from_utf8(container).unwrap();
+        contents = contents.replacen(&fingerprint(alice.public.as_bytes()), "ab", 1);
+
+        assert!(inspect(contents.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn mnemonic_seed_survives_a_roundtrip() {
+        let (phrase, seed) = generate_mnemonic_seed().unwrap();
+        let recovered_seed = seed_from_mnemonic(&phrase).unwrap();
+        assert_eq!(seed, recovered_seed);
+    }
+
+    #[test]
+    fn same_mnemonic_derives_the_same_keypair() {
+        let (phrase, seed) = generate_mnemonic_seed().unwrap();
+        let seed_again = seed_from_mnemonic(&phrase).unwrap();
+
+        let keypair = KeyPair::generate_deterministic(&seed);
+        let keypair_again = KeyPair::generate_deterministic(&seed_again);
+
+        assert_eq!(keypair.public.as_bytes(), keypair_again.public.as_bytes());
+    }
+}
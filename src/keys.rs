@@ -0,0 +1,59 @@
+// src/keys.rs
+use rand_chacha::ChaCha20Rng;
+use rand_core::{OsRng, SeedableRng};
+use std::{fs, io, path::Path};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// An X25519 keypair, serialized to disk as hex-encoded `.pub`/`.key` files.
+#[derive(Debug)]
+pub struct KeyPair {
+    pub public: PublicKey,
+    pub private: StaticSecret,
+}
+
+impl KeyPair {
+    pub fn generate() -> Self {
+        let private = StaticSecret::random_from_rng(OsRng);
+        let public = PublicKey::from(&private);
+        KeyPair { public, private }
+    }
+
+    /// Derives a keypair from 64 bytes of seed material (e.g. the PBKDF2
+    /// output of a BIP-39 mnemonic) by seeding a CSPRNG with its first 32
+    /// bytes, so the same seed always reproduces the same keypair. The last
+    /// 32 bytes of the seed are not used.
+    pub fn generate_deterministic(seed: &[u8; 64]) -> Self {
+        let mut rng_seed = [0u8; 32];
+        rng_seed.copy_from_slice(&seed[..32]);
+        let mut rng = ChaCha20Rng::from_seed(rng_seed);
+        let private = StaticSecret::random_from_rng(&mut rng);
+        let public = PublicKey::from(&private);
+        KeyPair { public, private }
+    }
+
+    pub fn save(&self, pub_path: &Path, priv_path: &Path) -> io::Result<()> {
+        fs::write(pub_path, hex::encode(self.public.as_bytes()))?;
+        fs::write(priv_path, hex::encode(self.private.to_bytes()))
+    }
+
+    pub fn load_public_key(path: &Path) -> io::Result<PublicKey> {
+        let bytes = decode_key_file(path)?;
+        Ok(PublicKey::from(bytes))
+    }
+
+    pub fn load_keypair(pub_path: &Path, priv_path: &Path) -> io::Result<KeyPair> {
+        let public = Self::load_public_key(pub_path)?;
+        let private = StaticSecret::from(decode_key_file(priv_path)?);
+        Ok(KeyPair { public, private })
+    }
+}
+
+fn decode_key_file(path: &Path) -> io::Result<[u8; 32]> {
+    let contents = fs::read_to_string(path)?;
+    let key_hex = contents.lines().next().unwrap_or("").trim();
+    let bytes = hex::decode(key_hex)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    bytes
+        .try_into()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "expected a 32-byte key"))
+}
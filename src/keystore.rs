@@ -0,0 +1,303 @@
+// src/keystore.rs
+use crate::crypto;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+/// Metadata about a single key file, parsed well enough to support
+/// `list-keys` filtering without needing to decode the key material itself.
+#[derive(Debug, Clone)]
+pub struct KeyInfo {
+    pub path: PathBuf,
+    pub name: String,
+    pub email: Option<String>,
+    pub fingerprint: String,
+    pub is_public: bool,
+}
+
+/// Owns the `~/.purr` directory tree: the public/private key directories and
+/// (via `home_dir`) the config file alongside them.
+pub struct Keystore {
+    pub home_dir: PathBuf,
+    pub keys_dir: PathBuf,
+}
+
+impl Keystore {
+    pub fn new() -> io::Result<Self> {
+        let home_dir = home_dir()?.join(".purr");
+        let keys_dir = home_dir.join("keys");
+        fs::create_dir_all(keys_dir.join("public"))?;
+        fs::create_dir_all(keys_dir.join("private"))?;
+        Ok(Keystore { home_dir, keys_dir })
+    }
+
+    /// Warns (rather than fails) if the private key directory is readable by
+    /// anyone other than the current user.
+    pub fn verify_permissions(&self) -> Result<(), String> {
+        #[cfg(unix)]
+        {
+            let private_dir = self.keys_dir.join("private");
+            let mode = fs::metadata(&private_dir)
+                .map_err(|e| e.to_string())?
+                .permissions()
+                .mode();
+            if mode & 0o077 != 0 {
+                return Err(format!(
+                    "{} is readable by other users; run `chmod 700 {}`",
+                    private_dir.display(),
+                    private_dir.display()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_key_paths(&self, name: &str) -> (PathBuf, PathBuf) {
+        (
+            self.keys_dir.join("public").join(format!("{name}.pub")),
+            self.keys_dir.join("private").join(format!("{name}.key")),
+        )
+    }
+
+    pub fn find_key(&self, name: &str, is_public: bool) -> io::Result<PathBuf> {
+        let (dir, ext) = if is_public {
+            ("public", "pub")
+        } else {
+            ("private", "key")
+        };
+        let path = self.keys_dir.join(dir).join(format!("{name}.{ext}"));
+        if path.exists() {
+            Ok(path)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no {dir} key named '{name}' in keystore"),
+            ))
+        }
+    }
+
+    pub fn import_key(&self, key_path: &Path, is_public: bool) -> io::Result<PathBuf> {
+        let file_name = key_path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing file name"))?;
+        let dest_dir = self
+            .keys_dir
+            .join(if is_public { "public" } else { "private" });
+        let dest = dest_dir.join(file_name);
+        fs::copy(key_path, &dest)?;
+        Ok(dest)
+    }
+
+    pub fn list_keys(&self) -> io::Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+        Ok((
+            list_dir(&self.keys_dir.join("public"))?,
+            list_dir(&self.keys_dir.join("private"))?,
+        ))
+    }
+
+    /// Like [`Keystore::list_keys`], but parses each key file into a
+    /// [`KeyInfo`] (owner name, embedded email/comment, fingerprint) so
+    /// callers can filter without re-reading files themselves.
+    pub fn list_key_infos(&self) -> io::Result<Vec<KeyInfo>> {
+        let public = list_dir(&self.keys_dir.join("public"))?
+            .into_iter()
+            .map(|path| key_info(path, true));
+        let private = list_dir(&self.keys_dir.join("private"))?
+            .into_iter()
+            .map(|path| key_info(path, false));
+        public.chain(private).collect()
+    }
+}
+
+/// Key files store the hex-encoded key on the first line, followed
+/// optionally by a comment line of the form `Name <email>`, mirroring how
+/// OpenSSH/PGP key comments work.
+fn key_info(path: PathBuf, is_public: bool) -> io::Result<KeyInfo> {
+    let contents = fs::read_to_string(&path)?;
+    let mut lines = contents.lines();
+
+    let key_hex = lines.next().unwrap_or("").trim();
+    let key_bytes: [u8; 32] = hex::decode(key_hex)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?
+        .try_into()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "expected a 32-byte key"))?;
+
+    // Fingerprints always hash a public key, so a private key's fingerprint
+    // matches its paired .pub entry and the recipient fingerprints `inspect`
+    // prints - not the raw secret scalar.
+    let public_bytes = if is_public {
+        key_bytes
+    } else {
+        *PublicKey::from(&StaticSecret::from(key_bytes)).as_bytes()
+    };
+    let fingerprint = crypto::fingerprint(&public_bytes);
+
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (name, email) = match lines.next().and_then(parse_comment) {
+        Some((name, email)) => (name, Some(email)),
+        None => (stem, None),
+    };
+
+    Ok(KeyInfo {
+        path,
+        name,
+        email,
+        fingerprint,
+        is_public,
+    })
+}
+
+/// Parses a `Name <email>` comment line, returning `None` if it isn't one.
+fn parse_comment(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    let (name, rest) = line.split_once('<')?;
+    let email = rest.strip_suffix('>')?;
+    Some((name.trim().to_string(), email.trim().to_string()))
+}
+
+/// Filter predicates for `list-keys`: names/emails/fingerprints are ORed
+/// within their own category and ANDed across categories; an empty category
+/// imposes no restriction.
+#[derive(Debug, Default)]
+pub struct KeyFilter<'a> {
+    pub names: &'a [String],
+    pub emails: &'a [String],
+    pub fingerprints: &'a [String],
+    pub public_only: bool,
+    pub private_only: bool,
+}
+
+impl KeyFilter<'_> {
+    pub fn matches(&self, info: &KeyInfo) -> bool {
+        if self.public_only && !info.is_public {
+            return false;
+        }
+        if self.private_only && info.is_public {
+            return false;
+        }
+        if !self.names.is_empty() && !self.names.iter().any(|n| info.name.eq_ignore_ascii_case(n)) {
+            return false;
+        }
+        if !self.emails.is_empty() {
+            let matches_any_email = info.email.as_deref().map_or(false, |email| {
+                self.emails.iter().any(|e| email.eq_ignore_ascii_case(e))
+            });
+            if !matches_any_email {
+                return false;
+            }
+        }
+        if !self.fingerprints.is_empty()
+            && !self
+                .fingerprints
+                .iter()
+                .any(|fp| info.fingerprint.starts_with(&fp.to_lowercase()))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+fn list_dir(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+fn home_dir() -> io::Result<PathBuf> {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME is not set"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(name: &str, email: Option<&str>, fingerprint: &str, is_public: bool) -> KeyInfo {
+        KeyInfo {
+            path: PathBuf::from(format!("{name}.key")),
+            name: name.to_string(),
+            email: email.map(str::to_string),
+            fingerprint: fingerprint.to_string(),
+            is_public,
+        }
+    }
+
+    #[test]
+    fn key_filter_empty_matches_everything() {
+        let filter = KeyFilter::default();
+        assert!(filter.matches(&info("alice", None, "abcd", true)));
+    }
+
+    #[test]
+    fn key_filter_name_is_case_insensitive_and_ored() {
+        let names = vec!["Bob".to_string()];
+        let filter = KeyFilter {
+            names: &names,
+            ..KeyFilter::default()
+        };
+        assert!(filter.matches(&info("bob", None, "abcd", true)));
+        assert!(!filter.matches(&info("alice", None, "abcd", true)));
+    }
+
+    #[test]
+    fn key_filter_fingerprint_matches_by_prefix() {
+        let fingerprints = vec!["ab".to_string()];
+        let filter = KeyFilter {
+            fingerprints: &fingerprints,
+            ..KeyFilter::default()
+        };
+        assert!(filter.matches(&info("alice", None, "abcdef", true)));
+        assert!(!filter.matches(&info("alice", None, "ffffff", true)));
+    }
+
+    #[test]
+    fn key_filter_public_private_only_are_exclusive() {
+        let public_filter = KeyFilter {
+            public_only: true,
+            ..KeyFilter::default()
+        };
+        let private_filter = KeyFilter {
+            private_only: true,
+            ..KeyFilter::default()
+        };
+        let public_key = info("alice", None, "abcd", true);
+        let private_key = info("alice", None, "abcd", false);
+        assert!(public_filter.matches(&public_key));
+        assert!(!public_filter.matches(&private_key));
+        assert!(private_filter.matches(&private_key));
+        assert!(!private_filter.matches(&public_key));
+    }
+
+    #[test]
+    fn private_key_info_fingerprint_matches_its_public_counterpart() {
+        let private = StaticSecret::from([7u8; 32]);
+        let public = PublicKey::from(&private);
+
+        let dir = std::env::temp_dir().join(format!("purrcrypt-keystore-test-{:p}", &private));
+        fs::create_dir_all(&dir).unwrap();
+        let pub_path = dir.join("alice.pub");
+        let priv_path = dir.join("alice.key");
+        fs::write(&pub_path, hex::encode(public.as_bytes())).unwrap();
+        fs::write(&priv_path, hex::encode(private.to_bytes())).unwrap();
+
+        let public_info = key_info(pub_path, true).unwrap();
+        let private_info = key_info(priv_path, false).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(public_info.fingerprint, private_info.fingerprint);
+    }
+}
@@ -0,0 +1,7 @@
+// src/lib.rs
+pub mod cipher;
+pub mod config;
+pub mod crypto;
+pub mod debug;
+pub mod keys;
+pub mod keystore;
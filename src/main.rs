@@ -4,24 +4,34 @@ use purrcrypt::{
     config::{ConfigManager, PreferredDialect},
     crypto, debug,
     keys::KeyPair,
-    keystore::Keystore,
+    keystore::{self, Keystore},
+};
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    path::Path,
+    process,
 };
-use std::{env, path::Path, process};
 
 #[derive(Debug)]
 enum Command {
     GenerateKey {
         name: Option<String>,
+        mnemonic: bool,
+    },
+    RecoverKey {
+        phrase: String,
+        name: Option<String>,
     },
     Encrypt {
-        recipient_key: String,
-        input_file: String,
+        recipient_keys: Vec<String>,
+        input_file: Option<String>,
         output_file: Option<String>,
         dialect: Option<String>,
     },
     Decrypt {
         private_key: String,
-        input_file: String,
+        input_file: Option<String>,
         output_file: Option<String>,
     },
     ImportKey {
@@ -31,7 +41,16 @@ enum Command {
     SetDialect {
         dialect: String,
     },
-    ListKeys,
+    ListKeys {
+        names: Vec<String>,
+        emails: Vec<String>,
+        fingerprints: Vec<String>,
+        public_only: bool,
+        private_only: bool,
+    },
+    Inspect {
+        input_file: Option<String>,
+    },
     Help,
 }
 
@@ -44,34 +63,73 @@ Usage:
 
 Commands:
     genkey [name]                   Generate a new keypair
+    genkey --mnemonic [name]        Generate a keypair and print its
+                                     recovery phrase
+    recover-key <phrase> [name]     Recover a keypair from a recovery phrase
     import-key [--public] <keyfile> Import a key
     encrypt, -e                     Encrypt a message
     decrypt, -d                     Decrypt a message
     list-keys, -k                   List known keys
+    inspect <file>                   Show a .purr container's structure
+                                     without decrypting it
     set-dialect <cat|dog>          Set preferred dialect
     verbose, -v                     Enable verbose debug output
 
 Options for encrypt:
-    -r, --recipient <key>          Recipient's public key or name
-    -o, --output <file>            Output file (default: adds .purr)
-    -i, --input <file>             Input file
+    -r, --recipient <key>          Recipient's public key or name (repeat
+                                    -r to encrypt to multiple recipients)
+    -o, --output <file>            Output file (default: adds .purr, or
+                                    stdout when reading stdin; '-' means
+                                    stdout)
+    -i, --input <file>             Input file ('-' or omitted means stdin)
     --dialect <cat|dog>            Override dialect for this encryption
 
 Options for decrypt:
     -k, --key <key>               Your private key or name
-    -o, --output <file>           Output file
-    -i, --input <file>            Input file
+    -o, --output <file>           Output file (default: strips .purr, or
+                                    stdout when reading stdin; '-' means
+                                    stdout)
+    -i, --input <file>            Input file ('-' or omitted means stdin)
+
+Options for list-keys:
+    --name <name>                 Only show keys owned by <name> (repeatable)
+    --email <email>               Only show keys with this embedded email
+                                    (repeatable)
+    --fingerprint <hex>            Only show keys whose fingerprint starts
+                                    with <hex> (repeatable)
+    --public-only, --private-only Restrict to one half of the keystore
 
 Examples:
     {} genkey                     # Generate keys as user.pub and user.key
     {} genkey alice               # Generate keys as alice.pub and alice.key
+    {} genkey --mnemonic alice    # Generate alice's keys with a recovery phrase
+    {} recover-key \"...\" alice    # Recover alice's keys from that phrase
     {} import-key bob.pub         # Import Bob's public key
     {} -e -r bob message.txt      # Encrypt for Bob using preferred dialect
     {} -e -r bob --dialect dog    # Encrypt for Bob using dog dialect
+    {} -e -r bob -r carol msg.txt # Encrypt for both Bob and Carol
     {} -d -k alice message.purr   # Decrypt using Alice's key
     {} set-dialect dog            # Switch to dog mode
-    {} -v -e -r bob msg.txt       # Encrypt with verbose output",
-        program, program, program, program, program, program, program, program, program
+    {} -k --name bob --public-only # List Bob's public keys
+    {} inspect message.purr       # Show who a container is encrypted for
+    {} -v -e -r bob msg.txt       # Encrypt with verbose output
+    cat msg.txt | {} -e -r bob | ssh host '{} -d -k me' > out.txt",
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program,
+        program
     );
 }
 
@@ -101,9 +159,22 @@ fn parse_args() -> Result<Command, String> {
                 dialect: dialect.clone(),
             })
         }
-        "genkey" => Ok(Command::GenerateKey {
-            name: filtered_args.get(2).cloned(),
-        }),
+        "genkey" => {
+            let rest: Vec<&String> = filtered_args[2..].iter().collect();
+            let mnemonic = rest.iter().any(|arg| arg.as_str() == "--mnemonic");
+            let name = rest
+                .into_iter()
+                .find(|arg| arg.as_str() != "--mnemonic")
+                .cloned();
+            Ok(Command::GenerateKey { name, mnemonic })
+        }
+        "recover-key" => {
+            let phrase = filtered_args.get(2).ok_or("Missing recovery phrase")?;
+            Ok(Command::RecoverKey {
+                phrase: phrase.clone(),
+                name: filtered_args.get(3).cloned(),
+            })
+        }
         "import-key" => {
             if filtered_args.len() < 3 {
                 return Err("Missing key file to import".to_string());
@@ -119,10 +190,59 @@ fn parse_args() -> Result<Command, String> {
                 is_public,
             })
         }
-        "list-keys" | "listkeys" | "-k" => Ok(Command::ListKeys),
+        "list-keys" | "listkeys" | "-k" => {
+            let mut i = 2;
+            let mut names = Vec::new();
+            let mut emails = Vec::new();
+            let mut fingerprints = Vec::new();
+            let mut public_only = false;
+            let mut private_only = false;
+
+            while i < filtered_args.len() {
+                match filtered_args[i].as_str() {
+                    "--name" => {
+                        names.push(filtered_args.get(i + 1).ok_or("Missing value for --name")?.clone());
+                        i += 2;
+                    }
+                    "--email" => {
+                        emails.push(filtered_args.get(i + 1).ok_or("Missing value for --email")?.clone());
+                        i += 2;
+                    }
+                    "--fingerprint" => {
+                        fingerprints.push(
+                            filtered_args
+                                .get(i + 1)
+                                .ok_or("Missing value for --fingerprint")?
+                                .clone(),
+                        );
+                        i += 2;
+                    }
+                    "--public-only" => {
+                        public_only = true;
+                        i += 1;
+                    }
+                    "--private-only" => {
+                        private_only = true;
+                        i += 1;
+                    }
+                    other => return Err(format!("Unknown list-keys option: {other}")),
+                }
+            }
+
+            Ok(Command::ListKeys {
+                names,
+                emails,
+                fingerprints,
+                public_only,
+                private_only,
+            })
+        }
+        "inspect" => Ok(Command::Inspect {
+            input_file: filtered_args.get(2).cloned(),
+        }),
         "encrypt" | "-e" => {
             let mut i = 2;
-            let mut recipient = None;
+            let mut recipients = Vec::new();
             let mut input = None;
             let mut output = None;
             let mut dialect = None;
@@ -130,7 +250,7 @@ fn parse_args() -> Result<Command, String> {
             while i < filtered_args.len() {
                 match filtered_args[i].as_str() {
                     "-r" | "--recipient" => {
-                        recipient = Some(filtered_args.get(i + 1).ok_or("Missing recipient")?);
+                        recipients.push(filtered_args.get(i + 1).ok_or("Missing recipient")?.clone());
                         i += 2;
                     }
                     "-o" | "--output" => {
@@ -155,9 +275,13 @@ fn parse_args() -> Result<Command, String> {
                 }
             }
 
+            if recipients.is_empty() {
+                return Err("Missing recipient (-r)".to_string());
+            }
+
             Ok(Command::Encrypt {
-                recipient_key: recipient.ok_or("Missing recipient (-r)")?.clone(),
-                input_file: input.ok_or("Missing input file")?.clone(),
+                recipient_keys: recipients,
+                input_file: input,
                 output_file: output,
                 dialect,
             })
@@ -195,7 +319,7 @@ fn parse_args() -> Result<Command, String> {
 
             Ok(Command::Decrypt {
                 private_key: key.ok_or("Missing private key (-k)")?.clone(),
-                input_file: input.ok_or("Missing input file")?.clone(),
+                input_file: input,
                 output_file: output,
             })
         }
@@ -203,6 +327,45 @@ fn parse_args() -> Result<Command, String> {
     }
 }
 
+/// Opens `path` for reading, or stdin when `path` is `None` or `"-"`.
+fn open_or_stdin(path: Option<&str>) -> io::Result<Box<dyn Read>> {
+    match path {
+        None | Some("-") => Ok(Box::new(io::stdin())),
+        Some(path) => Ok(Box::new(fs::File::open(path)?)),
+    }
+}
+
+/// Creates `path` for writing, or stdout when `path` is `None` or `"-"`.
+fn create_or_stdout(path: Option<&str>) -> io::Result<Box<dyn Write>> {
+    match path {
+        None | Some("-") => Ok(Box::new(io::stdout())),
+        Some(path) => Ok(Box::new(fs::File::create(path)?)),
+    }
+}
+
+/// Runs a configured hook command (e.g. `pre_encrypt`) against `path`,
+/// passed as its sole argument through `sh -c`. Returns an error - aborting
+/// the surrounding operation - if the hook exits non-zero.
+fn run_hook(hook: &str, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let status = process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{hook} \"$0\""))
+        .arg(path)
+        .status()?;
+    if !status.success() {
+        return Err(format!("hook `{hook}` failed on {path}").into());
+    }
+    Ok(())
+}
+
+fn print_key_info(info: &keystore::KeyInfo) {
+    print!("  {} ({}...)", info.name, &info.fingerprint[..16]);
+    if let Some(email) = &info.email {
+        print!(" <{}>", email);
+    }
+    println!();
+}
+
 fn run() -> Result<(), Box<dyn std::error::Error>> {
     let keystore = Keystore::new()?;
     let mut config_manager = ConfigManager::new(&keystore.home_dir)?;
@@ -219,8 +382,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
     });
 
     match command {
-        Command::GenerateKey { name } => {
-            println!("🐱 Generating new keypair...");
+        Command::GenerateKey { name, mnemonic } => {
             let name = name.unwrap_or_else(|| "default".to_string());
             let pub_path = keystore
                 .keys_dir
@@ -231,11 +393,43 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 .join("private")
                 .join(format!("{}.key", name));
 
-            crypto::generate_keypair(&pub_path, &priv_path)?;
+            if mnemonic {
+                println!("🐱 Generating new keypair from a recovery phrase...");
+                let (phrase, seed) = crypto::generate_mnemonic_seed()?;
+                crypto::generate_keypair_from_seed(&pub_path, &priv_path, &seed)?;
+                println!();
+                println!("📝 Write this phrase down somewhere safe - it's the only way to");
+                println!("   recover these keys if you lose them:");
+                println!();
+                println!("    {}", phrase);
+                println!();
+            } else {
+                println!("🐱 Generating new keypair...");
+                crypto::generate_keypair(&pub_path, &priv_path)?;
+            }
+
             println!("✨ Generated keys:");
             println!("  Public key:  {}", pub_path.display());
             println!("  Private key: {}", priv_path.display());
         }
+        Command::RecoverKey { phrase, name } => {
+            println!("🐱 Recovering keypair from recovery phrase...");
+            let name = name.unwrap_or_else(|| "default".to_string());
+            let pub_path = keystore
+                .keys_dir
+                .join("public")
+                .join(format!("{}.pub", name));
+            let priv_path = keystore
+                .keys_dir
+                .join("private")
+                .join(format!("{}.key", name));
+
+            let seed = crypto::seed_from_mnemonic(&phrase)?;
+            crypto::generate_keypair_from_seed(&pub_path, &priv_path, &seed)?;
+            println!("✨ Recovered keys:");
+            println!("  Public key:  {}", pub_path.display());
+            println!("  Private key: {}", priv_path.display());
+        }
         Command::SetDialect { dialect } => {
             let new_dialect = match dialect.to_lowercase().as_str() {
                 "cat" => {
@@ -251,12 +445,19 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
             config_manager.set_dialect(new_dialect)?;
         }
         Command::Encrypt {
-            recipient_key,
+            recipient_keys,
             input_file,
             output_file,
             dialect,
         } => {
-            let output = output_file.unwrap_or_else(|| format!("{}.purr", input_file));
+            // Default output to "<input>.purr", unless input itself is stdin,
+            // in which case we fall through to stdout.
+            let output_path = output_file.or_else(|| {
+                input_file
+                    .as_deref()
+                    .filter(|path| *path != "-")
+                    .map(|path| format!("{}.purr", path))
+            });
 
             // Use command-line dialect if specified, otherwise use config
             let dialect = match dialect {
@@ -276,18 +477,42 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 CipherDialect::Dog => "🐕",
             };
 
-            println!(
+            eprintln!(
                 "{} Encrypting {} for {}",
-                mode_emoji, input_file, recipient_key
+                mode_emoji,
+                input_file.as_deref().unwrap_or("-"),
+                recipient_keys.join(", ")
             );
 
-            let key_path = keystore
-                .find_key(&recipient_key, true)
-                .unwrap_or_else(|_| Path::new(&recipient_key).to_path_buf());
+            let recipient_public_keys = recipient_keys
+                .iter()
+                .map(|recipient_key| {
+                    let key_path = keystore
+                        .find_key(recipient_key, true)
+                        .unwrap_or_else(|_| Path::new(recipient_key).to_path_buf());
+                    KeyPair::load_public_key(&key_path)
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+
+            if let (Some(hook), Some(path)) = (config_manager.pre_encrypt_hook(), input_file.as_deref()) {
+                if path != "-" {
+                    run_hook(hook, path)?;
+                }
+            }
+
+            let reader = open_or_stdin(input_file.as_deref())?;
+            let writer = create_or_stdout(output_path.as_deref())?;
+            crypto::encrypt_file(reader, writer, &recipient_public_keys, dialect)?;
+            eprintln!(
+                "✨ Encrypted message saved to {}",
+                output_path.as_deref().unwrap_or("-")
+            );
 
-            let recipient_public_key = KeyPair::load_public_key(&key_path)?;
-            crypto::encrypt_file(&input_file, &output, &recipient_public_key, dialect)?;
-            println!("✨ Encrypted message saved to {}", output);
+            if let (Some(hook), Some(path)) = (config_manager.post_encrypt_hook(), output_path.as_deref()) {
+                if path != "-" {
+                    run_hook(hook, path)?;
+                }
+            }
         }
 
         Command::Decrypt {
@@ -295,11 +520,20 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
             input_file,
             output_file,
         } => {
-            let output = output_file.unwrap_or_else(|| {
-                input_file
-                    .strip_suffix(".purr")
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| format!("{}.decrypted", input_file))
+            // Default output to the input minus its ".purr" suffix, unless
+            // input itself is stdin, in which case we fall through to stdout.
+            let output_path = output_file.or_else(|| {
+                input_file.as_deref().and_then(|path| {
+                    if path == "-" {
+                        None
+                    } else {
+                        Some(
+                            path.strip_suffix(".purr")
+                                .map(|s| s.to_string())
+                                .unwrap_or_else(|| format!("{}.decrypted", path)),
+                        )
+                    }
+                })
             });
 
             // Get both key paths based on the private key name
@@ -314,13 +548,33 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 process::exit(1);
             }
 
-            println!("🔓 Decrypting {} using:", input_file);
-            println!("   Private key: {}", priv_path.display());
-            println!("   Public key:  {}", pub_path.display());
+            eprintln!(
+                "🔓 Decrypting {} using:",
+                input_file.as_deref().unwrap_or("-")
+            );
+            eprintln!("   Private key: {}", priv_path.display());
+            eprintln!("   Public key:  {}", pub_path.display());
+
+            if let (Some(hook), Some(path)) = (config_manager.pre_decrypt_hook(), input_file.as_deref()) {
+                if path != "-" {
+                    run_hook(hook, path)?;
+                }
+            }
 
             let keypair = KeyPair::load_keypair(&pub_path, &priv_path)?;
-            crypto::decrypt_file(&input_file, &output, &keypair)?;
-            println!("✨ Decrypted message saved to {}", output);
+            let reader = open_or_stdin(input_file.as_deref())?;
+            let writer = create_or_stdout(output_path.as_deref())?;
+            crypto::decrypt_file(reader, writer, &keypair)?;
+            eprintln!(
+                "✨ Decrypted message saved to {}",
+                output_path.as_deref().unwrap_or("-")
+            );
+
+            if let (Some(hook), Some(path)) = (config_manager.post_decrypt_hook(), output_path.as_deref()) {
+                if path != "-" {
+                    run_hook(hook, path)?;
+                }
+            }
         }
 
         Command::ImportKey {
@@ -330,18 +584,52 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
             let path = keystore.import_key(Path::new(&key_path), is_public)?;
             println!("✨ Imported key to {}", path.display());
         }
-        Command::ListKeys => {
-            let (public_keys, private_keys) = keystore.list_keys()?;
+        Command::ListKeys {
+            names,
+            emails,
+            fingerprints,
+            public_only,
+            private_only,
+        } => {
+            let filter = keystore::KeyFilter {
+                names: &names,
+                emails: &emails,
+                fingerprints: &fingerprints,
+                public_only,
+                private_only,
+            };
+
+            let (public_keys, private_keys): (Vec<_>, Vec<_>) = keystore
+                .list_key_infos()?
+                .into_iter()
+                .filter(|info| filter.matches(info))
+                .partition(|info| info.is_public);
 
             println!("🔑 Public keys in ~/.purr/keys/public/:");
-            for key in public_keys {
-                println!("  {}", key.file_name().unwrap().to_string_lossy());
+            for info in &public_keys {
+                print_key_info(info);
             }
 
             println!("\n🔐 Private keys in ~/.purr/keys/private/:");
-            for key in private_keys {
-                println!("  {}", key.file_name().unwrap().to_string_lossy());
+            for info in &private_keys {
+                print_key_info(info);
+            }
+        }
+        Command::Inspect { input_file } => {
+            let reader = open_or_stdin(input_file.as_deref())?;
+            let info = crypto::inspect(reader)?;
+
+            println!("🔍 {}", input_file.as_deref().unwrap_or("-"));
+            println!("  Dialect:          {}", info.dialect);
+            println!("  Recipients:       {}", info.recipient_fingerprints.len());
+            for fingerprint in &info.recipient_fingerprints {
+                println!("    {}...", &fingerprint[..16]);
             }
+            println!("  Payload length:   {} bytes", info.payload_len);
+            println!(
+                "  Cipher tokens:    {} total, {} distinct",
+                info.token_stats.total_tokens, info.token_stats.distinct_tokens
+            );
         }
         Command::Help => {
             print_usage(&env::args().next().unwrap_or_else(|| "purr".to_string()));